@@ -1,11 +1,16 @@
 use colored::*;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
 
 use crate::hyperfine::types::{BenchmarkResult, OutputStyleOption};
 use crate::hyperfine::units::{Scalar, Second};
 
 use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
 use std::iter::Iterator;
+use std::path::Path;
 
 /// Threshold for warning about fast execution time
 pub const MIN_EXECUTION_TIME: Second = 5e-3;
@@ -46,10 +51,216 @@ pub fn min(vals: &[f64]) -> f64 {
         .unwrap()
 }
 
+/// Number of bootstrap resamples used to derive the relative speed confidence interval.
+pub const DEFAULT_BOOTSTRAP_SAMPLES: usize = 100_000;
+
+/// Options controlling the bootstrap resampling used by `compute_relative_speed`.
+#[derive(Debug, Clone, Copy)]
+pub struct BootstrapConfig {
+    pub samples: usize,
+    /// Seed for the resampling PRNG.
+    pub seed: u64,
+}
+
+impl Default for BootstrapConfig {
+    fn default() -> Self {
+        BootstrapConfig {
+            samples: DEFAULT_BOOTSTRAP_SAMPLES,
+            seed: 0x2545_f491_4f6c_dd1d,
+        }
+    }
+}
+
+/// A small xorshift64 PRNG, to avoid a dependency for resampling a few f64 slices.
+struct Xorshift64 {
+    state: u64,
+}
+
+impl Xorshift64 {
+    fn new(seed: u64) -> Self {
+        Xorshift64 {
+            state: if seed == 0 { 0xdead_beef } else { seed },
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.state = x;
+        x
+    }
+
+    /// Return a pseudo-random index in `0..n`.
+    fn next_index(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+fn resample_mean(times: &[Second], rng: &mut Xorshift64) -> Second {
+    let n = times.len();
+    let sum: Second = (0..n).map(|_| times[rng.next_index(n)]).sum();
+    sum / (n as Second)
+}
+
+/// Resample `times` with replacement `config.samples` times, returning each resample's mean.
+fn bootstrap_resample_means(times: &[Second], config: &BootstrapConfig) -> Vec<Second> {
+    let mut rng = Xorshift64::new(config.seed);
+    (0..config.samples)
+        .map(|_| resample_mean(times, &mut rng))
+        .collect()
+}
+
+/// Derive an independent per-comparison seed (SplitMix64-style) from the configured seed.
+fn seed_for_comparison(seed: u64, index: usize) -> u64 {
+    let mut z = seed.wrapping_add(
+        (index as u64)
+            .wrapping_add(1)
+            .wrapping_mul(0x9e37_79b9_7f4a_7c15),
+    );
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Compute a 95% bootstrap confidence interval for a command's relative speed against
+/// the fastest command. Returns `None` if `result_times` or `fastest_bootstrap_means` is
+/// empty, or if every resampled fastest mean is zero, in which case callers should fall
+/// back to error propagation.
+fn bootstrap_relative_speed_ci(
+    result_times: &[Second],
+    fastest_bootstrap_means: &[Second],
+    seed: u64,
+) -> Option<(Scalar, Scalar)> {
+    if result_times.is_empty() || fastest_bootstrap_means.is_empty() {
+        return None;
+    }
+
+    let mut rng = Xorshift64::new(seed);
+    let mut ratios = Vec::with_capacity(fastest_bootstrap_means.len());
+
+    for &fastest_mean in fastest_bootstrap_means {
+        if fastest_mean == 0.0 {
+            continue;
+        }
+        let result_mean = resample_mean(result_times, &mut rng);
+        ratios.push(result_mean / fastest_mean);
+    }
+
+    if ratios.is_empty() {
+        return None;
+    }
+
+    ratios.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let lower = percentile_of_sorted(&ratios, 2.5);
+    let upper = percentile_of_sorted(&ratios, 97.5);
+    Some((lower, upper))
+}
+
+/// Linear-interpolated percentile of an already-sorted slice, `p` in `[0, 100]`.
+fn percentile_of_sorted(sorted: &[Scalar], p: f64) -> Scalar {
+    if sorted.len() == 1 {
+        return sorted[0];
+    }
+    let rank = (p / 100.0) * ((sorted.len() - 1) as f64);
+    let lower_index = rank.floor() as usize;
+    let upper_index = rank.ceil() as usize;
+    if lower_index == upper_index {
+        return sorted[lower_index];
+    }
+    let weight = rank - lower_index as f64;
+    sorted[lower_index] * (1.0 - weight) + sorted[upper_index] * weight
+}
+
+/// p-value threshold below which a relative speed difference is considered significant.
+pub const SIGNIFICANCE_THRESHOLD: f64 = 0.05;
+
+/// The standard normal cumulative distribution function.
+fn standard_normal_cdf(z: f64) -> f64 {
+    0.5 * erfc(-z / std::f64::consts::SQRT_2)
+}
+
+/// Complementary error function, accurate to within ~1.5e-7.
+fn erfc(x: f64) -> f64 {
+    let sign = if x < 0.0 { -1.0 } else { 1.0 };
+    let x = x.abs();
+
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let y = 1.0
+        - (((((1.061405429 * t - 1.453152027) * t) + 1.421413741) * t - 0.284496736) * t
+            + 0.254829592)
+            * t
+            * (-x * x).exp();
+
+    1.0 - sign * y
+}
+
+/// Two-sided p-value from a Mann-Whitney U test comparing `a` against `b`. Returns
+/// `None` if either sample is empty.
+fn mann_whitney_u_test(a: &[Second], b: &[Second]) -> Option<f64> {
+    let n1 = a.len();
+    let n2 = b.len();
+    if n1 == 0 || n2 == 0 {
+        return None;
+    }
+
+    let mut combined: Vec<(Second, u8)> = a
+        .iter()
+        .map(|&v| (v, 0))
+        .chain(b.iter().map(|&v| (v, 1)))
+        .collect();
+    combined.sort_by(|l, r| l.0.partial_cmp(&r.0).unwrap_or(Ordering::Equal));
+
+    let mut ranks = vec![0.0; combined.len()];
+    let mut tie_correction = 0.0;
+    let mut i = 0;
+    while i < combined.len() {
+        let mut j = i;
+        while j + 1 < combined.len() && combined[j + 1].0 == combined[i].0 {
+            j += 1;
+        }
+        // Tied observations share the average of the ranks they span (1-indexed).
+        let average_rank = (i + j) as f64 / 2.0 + 1.0;
+        for rank in ranks.iter_mut().take(j + 1).skip(i) {
+            *rank = average_rank;
+        }
+        let tie_count = (j - i + 1) as f64;
+        tie_correction += tie_count.powi(3) - tie_count;
+        i = j + 1;
+    }
+
+    let rank_sum_a: f64 = combined
+        .iter()
+        .zip(ranks.iter())
+        .filter(|((_, group), _)| *group == 0)
+        .map(|(_, rank)| rank)
+        .sum();
+
+    let u_a = rank_sum_a - (n1 * (n1 + 1)) as f64 / 2.0;
+    let u = u_a.min((n1 * n2) as f64 - u_a);
+
+    let n = (n1 + n2) as f64;
+    let mean_u = (n1 * n2) as f64 / 2.0;
+    let variance_u = (n1 * n2) as f64 / 12.0 * (n + 1.0 - tie_correction / (n * (n - 1.0)));
+    if variance_u <= 0.0 {
+        return Some(1.0);
+    }
+    let z = (u - mean_u) / variance_u.sqrt();
+
+    Some(2.0 * standard_normal_cdf(-z.abs()))
+}
+
 pub struct BenchmarkResultWithRelativeSpeed<'a> {
     pub result: &'a BenchmarkResult,
     pub relative_speed: Scalar,
     pub relative_speed_stddev: Scalar,
+    /// Bootstrap 95% CI for `relative_speed`. `None` for the fastest command itself and
+    /// when raw timings weren't recorded.
+    pub relative_speed_ci: Option<(Scalar, Scalar)>,
+    /// Two-sided p-value from a Mann-Whitney U test against the fastest command. `None`
+    /// for the fastest command itself and when raw timings weren't recorded.
+    pub p_value: Option<f64>,
     pub percent_change: Scalar,
     pub is_fastest: bool,
 }
@@ -60,15 +271,25 @@ fn compare_mean_time(l: &BenchmarkResult, r: &BenchmarkResult) -> Ordering {
 
 pub fn compute_relative_speed<'a>(
     results: &'a [BenchmarkResult],
+    bootstrap: &BootstrapConfig,
 ) -> Vec<BenchmarkResultWithRelativeSpeed<'a>> {
     let fastest: &BenchmarkResult = results
         .iter()
         .min_by(|&l, &r| compare_mean_time(l, r))
         .expect("at least one benchmark result");
 
+    // Precompute once and reuse for every comparison below.
+    let fastest_bootstrap_means = fastest
+        .times
+        .as_ref()
+        .filter(|times| !times.is_empty())
+        .map(|times| bootstrap_resample_means(times, bootstrap))
+        .unwrap_or_default();
+
     results
         .iter()
-        .map(|result| {
+        .enumerate()
+        .map(|(index, result)| {
             let ratio = result.mean / fastest.mean;
             let percent_change = 100.0 * (result.mean - fastest.mean) / result.mean;
 
@@ -78,23 +299,371 @@ pub fn compute_relative_speed<'a>(
                 * ((result.stddev / result.mean).powi(2) + (fastest.stddev / fastest.mean).powi(2))
                     .sqrt();
 
+            let is_fastest = result == fastest;
+
+            // Never compared against itself, so skip the bootstrap and Mann-Whitney work.
+            let (relative_speed_ci, p_value) = if is_fastest {
+                (None, None)
+            } else {
+                let relative_speed_ci = result.times.as_ref().and_then(|result_times| {
+                    bootstrap_relative_speed_ci(
+                        result_times,
+                        &fastest_bootstrap_means,
+                        seed_for_comparison(bootstrap.seed, index),
+                    )
+                });
+                let p_value = match (&result.times, &fastest.times) {
+                    (Some(a), Some(b)) => mann_whitney_u_test(a, b),
+                    _ => None,
+                };
+                (relative_speed_ci, p_value)
+            };
+
             BenchmarkResultWithRelativeSpeed {
                 result,
                 relative_speed: ratio,
                 relative_speed_stddev: ratio_stddev,
+                relative_speed_ci,
+                p_value,
                 percent_change: percent_change,
-                is_fastest: result == fastest,
+                is_fastest,
             }
         })
         .collect()
 }
 
-pub fn write_benchmark_comparison(results: &[BenchmarkResult]) {
-    if results.len() < 2 {
+/// Tukey fence multipliers distinguishing mild from severe outliers.
+const MILD_OUTLIER_FENCE: f64 = 1.5;
+const SEVERE_OUTLIER_FENCE: f64 = 3.0;
+
+/// Count of timings falling outside the mild and severe Tukey fences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutlierCounts {
+    pub mild: usize,
+    pub severe: usize,
+}
+
+impl OutlierCounts {
+    fn is_empty(&self) -> bool {
+        self.mild == 0 && self.severe == 0
+    }
+}
+
+/// Classify `times` against the mild and severe Tukey fences of their IQR.
+pub fn detect_outliers(times: &[Second]) -> OutlierCounts {
+    if times.len() < 4 {
+        return OutlierCounts { mild: 0, severe: 0 };
+    }
+
+    let mut sorted = times.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let q1 = percentile_of_sorted(&sorted, 25.0);
+    let q3 = percentile_of_sorted(&sorted, 75.0);
+    let iqr = q3 - q1;
+
+    let mild_lower = q1 - MILD_OUTLIER_FENCE * iqr;
+    let mild_upper = q3 + MILD_OUTLIER_FENCE * iqr;
+    let severe_lower = q1 - SEVERE_OUTLIER_FENCE * iqr;
+    let severe_upper = q3 + SEVERE_OUTLIER_FENCE * iqr;
+
+    let mut counts = OutlierCounts { mild: 0, severe: 0 };
+    for &t in times {
+        if t < severe_lower || t > severe_upper {
+            counts.severe += 1;
+        } else if t < mild_lower || t > mild_upper {
+            counts.mild += 1;
+        }
+    }
+
+    counts
+}
+
+/// Print `result`'s outlier counts, if any, and an advisory if severe outliers
+/// exceed the `--warn-on-outliers` fraction.
+fn print_outlier_warning(result: &BenchmarkResult, warn_on_outliers: Option<f64>) {
+    let times = match &result.times {
+        Some(times) => times,
+        None => return,
+    };
+
+    let counts = detect_outliers(times);
+    if counts.is_empty() {
         return;
     }
 
-    let mut annotated_results = compute_relative_speed(&results);
+    println!(
+        "  {} {} (mild), {} (severe) outliers detected for '{}'",
+        "Warning:".yellow().bold(),
+        counts.mild,
+        counts.severe,
+        result.command.cyan(),
+    );
+
+    if let Some(threshold) = warn_on_outliers {
+        let severe_fraction = counts.severe as f64 / times.len() as f64;
+        if severe_fraction > threshold {
+            println!(
+                "  {} A large fraction of runs were severe outliers. This could be caused \
+                 by a background process or a cold cache. Consider using the '--warmup' \
+                 option to reduce the impact of non-stationary conditions.",
+                "Note:".yellow(),
+            );
+        }
+    }
+}
+
+/// Decimal SI magnitude prefixes used to scale a throughput rate for display.
+const THROUGHPUT_PREFIXES: [&str; 5] = ["", "K", "M", "G", "T"];
+
+/// The work performed by a single benchmarked invocation, as given via `--throughput`.
+#[derive(Debug, Clone)]
+pub struct ThroughputOptions {
+    /// Number of units (bytes, elements, iterations, ...) processed per invocation.
+    pub units_per_run: f64,
+    /// Label for a single unit, e.g. "B" or "elem".
+    pub unit: String,
+}
+
+/// Decimal places for a scaled throughput value -- fewer digits as it grows.
+fn throughput_precision(scaled: f64) -> usize {
+    if scaled >= 100.0 {
+        0
+    } else if scaled >= 10.0 {
+        1
+    } else {
+        2
+    }
+}
+
+fn format_throughput(units_per_second: f64, unit: &str) -> String {
+    if !units_per_second.is_finite() || units_per_second <= 0.0 {
+        return format!("0 {}/s", unit);
+    }
+
+    let max_magnitude = THROUGHPUT_PREFIXES.len() - 1;
+    let mut magnitude = ((units_per_second.log10() / 3.0).floor() as isize)
+        .max(0)
+        .min(max_magnitude as isize) as usize;
+    let mut scaled = units_per_second / 1000f64.powi(magnitude as i32);
+
+    // Rounding can push `scaled` across a magnitude or precision-tier threshold
+    // (e.g. 999,999 -> "1.00 M..." or 9996 -> "10.0 K..."); recompute until stable.
+    let mut precision = throughput_precision(scaled);
+    loop {
+        let rounding_factor = 10f64.powi(precision as i32);
+        let rounded = (scaled * rounding_factor).round() / rounding_factor;
+        if rounded >= 1000.0 && magnitude < max_magnitude {
+            magnitude += 1;
+            scaled /= 1000.0;
+            precision = throughput_precision(scaled);
+            continue;
+        }
+        let new_precision = throughput_precision(rounded);
+        if new_precision != precision {
+            precision = new_precision;
+            continue;
+        }
+        break;
+    }
+
+    format!(
+        "{:.*} {}{}/s",
+        precision, scaled, THROUGHPUT_PREFIXES[magnitude], unit
+    )
+}
+
+/// Print `result`'s throughput, if given via `--throughput`.
+fn print_throughput(result: &BenchmarkResult, throughput: Option<&ThroughputOptions>) {
+    if let Some(throughput) = throughput {
+        let rate = throughput.units_per_run / result.mean;
+        println!(
+            "    {} {}",
+            "Throughput:".dimmed(),
+            format_throughput(rate, &throughput.unit)
+        );
+    }
+}
+
+/// Noise tolerance for regression detection, as a multiple of the baseline's stddev.
+pub const DEFAULT_RATCHET_NOISE: Scalar = 1.0;
+
+/// A single command's recorded timing, as persisted in a baseline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub mean: Second,
+    pub stddev: Second,
+}
+
+/// A set of baseline timings keyed by command string, for `--baseline`/`--save-baseline`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Baseline(BTreeMap<String, BaselineEntry>);
+
+impl Baseline {
+    pub fn from_results(results: &[BenchmarkResult]) -> Self {
+        Baseline(
+            results
+                .iter()
+                .map(|result| {
+                    (
+                        result.command.clone(),
+                        BaselineEntry {
+                            mean: result.mean,
+                            stddev: result.stddev,
+                        },
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json = fs::read_to_string(path)?;
+        serde_json::from_str(&json).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    pub fn get(&self, command: &str) -> Option<&BaselineEntry> {
+        self.0.get(command)
+    }
+
+    pub fn update_entry(&mut self, command: &str, entry: BaselineEntry) {
+        self.0.insert(command.to_string(), entry);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegressionStatus {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+/// Classify `current` against `baseline`, treating the change as real only once it
+/// exceeds `ratchet_noise * baseline.stddev`.
+pub fn classify_regression(
+    current: &BenchmarkResult,
+    baseline: &BaselineEntry,
+    ratchet_noise: Scalar,
+) -> RegressionStatus {
+    let tolerance = ratchet_noise * baseline.stddev;
+    let delta = current.mean - baseline.mean;
+    if delta.abs() <= tolerance {
+        RegressionStatus::Unchanged
+    } else if delta > 0.0 {
+        RegressionStatus::Regressed
+    } else {
+        RegressionStatus::Improved
+    }
+}
+
+/// Options controlling how `write_benchmark_comparison` classifies and reports changes
+/// against a stored baseline.
+#[derive(Debug, Clone, Copy)]
+pub struct BaselineComparisonOptions {
+    pub ratchet_noise: Scalar,
+    /// Bump a command's recorded baseline on improvement ("ratchet" behavior).
+    pub auto_update: bool,
+}
+
+impl Default for BaselineComparisonOptions {
+    fn default() -> Self {
+        BaselineComparisonOptions {
+            ratchet_noise: DEFAULT_RATCHET_NOISE,
+            auto_update: false,
+        }
+    }
+}
+
+/// Print `result`'s status against `baseline`, if one was given via `--baseline`.
+/// Returns `true` if `result` regressed, for `--fail-on-regression`.
+fn print_baseline_comparison(
+    result: &BenchmarkResult,
+    baseline: Option<&mut Baseline>,
+    options: &BaselineComparisonOptions,
+) -> bool {
+    let baseline = match baseline {
+        Some(baseline) => baseline,
+        None => return false,
+    };
+
+    let entry = match baseline.get(&result.command) {
+        Some(entry) => entry.clone(),
+        None => {
+            println!("    {} no baseline recorded", "Baseline:".dimmed());
+            return false;
+        }
+    };
+
+    let delta_percent = 100.0 * (result.mean - entry.mean) / entry.mean;
+    let status = classify_regression(result, &entry, options.ratchet_noise);
+
+    match status {
+        RegressionStatus::Improved => {
+            println!(
+                "    {} {} ({:+.1}%)",
+                "Baseline:".dimmed(),
+                "improved".green().bold(),
+                delta_percent
+            );
+            if options.auto_update {
+                baseline.update_entry(
+                    &result.command,
+                    BaselineEntry {
+                        mean: result.mean,
+                        stddev: result.stddev,
+                    },
+                );
+            }
+            false
+        }
+        RegressionStatus::Regressed => {
+            println!(
+                "    {} {} ({:+.1}%)",
+                "Baseline:".dimmed(),
+                "regressed".red().bold(),
+                delta_percent
+            );
+            true
+        }
+        RegressionStatus::Unchanged => {
+            println!(
+                "    {} unchanged ({:+.1}%)",
+                "Baseline:".dimmed(),
+                delta_percent
+            );
+            false
+        }
+    }
+}
+
+pub fn write_benchmark_comparison(
+    results: &[BenchmarkResult],
+    warn_on_outliers: Option<f64>,
+    throughput: Option<&ThroughputOptions>,
+    mut baseline: Option<&mut Baseline>,
+    baseline_options: &BaselineComparisonOptions,
+    bootstrap: &BootstrapConfig,
+) -> bool {
+    if results.is_empty() {
+        return false;
+    }
+
+    // Per-command checks run even with a single result; only "faster than" needs two.
+    if results.len() < 2 {
+        let result = &results[0];
+        println!("{}", "Summary".bold());
+        println!("  '{}' ran", result.command.cyan());
+        print_outlier_warning(result, warn_on_outliers);
+        print_throughput(result, throughput);
+        return print_baseline_comparison(result, baseline.as_deref_mut(), baseline_options);
+    }
+
+    let mut annotated_results = compute_relative_speed(&results, bootstrap);
     annotated_results.sort_by(|l, r| compare_mean_time(l.result, r.result));
 
     let fastest = &annotated_results[0];
@@ -102,16 +671,49 @@ pub fn write_benchmark_comparison(results: &[BenchmarkResult]) {
 
     println!("{}", "Summary".bold());
     println!("  '{}' ran", fastest.result.command.cyan());
+    print_outlier_warning(fastest.result, warn_on_outliers);
+    print_throughput(fastest.result, throughput);
+    let mut any_regressed =
+        print_baseline_comparison(fastest.result, baseline.as_deref_mut(), baseline_options);
 
     for item in others {
-        println!(
-            "{} ± {} times faster than '{}', -{}%",
-            format!("{:8.2}", item.relative_speed).bold().green(),
-            format!("{:.2}", item.relative_speed_stddev).green(),
-            &item.result.command.magenta(),
-            format!("{:.1}", item.percent_change).bold().green(),
-        );
+        match item.relative_speed_ci {
+            Some((lower, upper)) => println!(
+                "{} [{}, {}] times faster than '{}', -{}%",
+                format!("{:8.2}", item.relative_speed).bold().green(),
+                format!("{:.2}", lower).green(),
+                format!("{:.2}", upper).green(),
+                &item.result.command.magenta(),
+                format!("{:.1}", item.percent_change).bold().green(),
+            ),
+            None => println!(
+                "{} ± {} times faster than '{}', -{}%",
+                format!("{:8.2}", item.relative_speed).bold().green(),
+                format!("{:.2}", item.relative_speed_stddev).green(),
+                &item.result.command.magenta(),
+                format!("{:.1}", item.percent_change).bold().green(),
+            ),
+        }
+
+        if let Some(p_value) = item.p_value {
+            if p_value > SIGNIFICANCE_THRESHOLD {
+                println!(
+                    "  {} (p = {:.3}, not significant at α = {})",
+                    "Note: no statistically significant difference from the fastest command"
+                        .yellow(),
+                    p_value,
+                    SIGNIFICANCE_THRESHOLD,
+                );
+            }
+        }
+
+        print_outlier_warning(item.result, warn_on_outliers);
+        print_throughput(item.result, throughput);
+        any_regressed |=
+            print_baseline_comparison(item.result, baseline.as_deref_mut(), baseline_options);
     }
+
+    any_regressed
 }
 
 #[test]
@@ -146,11 +748,113 @@ fn test_compute_relative_speed() {
         create_result("cmd3", 5.0),
     ];
 
-    let annotated_results = compute_relative_speed(&results);
+    let annotated_results = compute_relative_speed(&results, &BootstrapConfig::default());
 
     assert_relative_eq!(1.5, annotated_results[0].relative_speed);
     assert_relative_eq!(1.0, annotated_results[1].relative_speed);
     assert_relative_eq!(2.5, annotated_results[2].relative_speed);
+    assert_eq!(None, annotated_results[0].relative_speed_ci);
+}
+
+#[test]
+fn test_compute_relative_speed_bootstrap_ci() {
+    let create_result = |name: &str, times: Vec<Second>| {
+        let mean = times.iter().sum::<Second>() / times.len() as Second;
+        BenchmarkResult {
+            command: name.into(),
+            mean,
+            stddev: 0.0,
+            median: mean,
+            user: mean,
+            system: 0.0,
+            min: times.iter().cloned().fold(f64::INFINITY, f64::min),
+            max: times.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+            times: Some(times),
+            parameter: None,
+        }
+    };
+
+    let results = vec![
+        create_result("fast", vec![1.0, 1.1, 0.9, 1.0, 1.0]),
+        create_result("slow", vec![2.0, 2.1, 1.9, 2.0, 2.0]),
+    ];
+
+    let bootstrap = BootstrapConfig {
+        samples: 1000,
+        seed: 42,
+    };
+    let annotated_results = compute_relative_speed(&results, &bootstrap);
+
+    let (lower, upper) = annotated_results[1]
+        .relative_speed_ci
+        .expect("bootstrap CI should be computed when raw timings are present");
+    assert!(lower <= annotated_results[1].relative_speed);
+    assert!(upper >= annotated_results[1].relative_speed);
+}
+
+#[test]
+fn test_bootstrap_relative_speed_ci_empty_times_falls_back() {
+    let config = BootstrapConfig::default();
+    let fastest_means = bootstrap_resample_means(&[1.0, 1.1, 0.9], &config);
+    let seed = seed_for_comparison(config.seed, 0);
+
+    // Empty `fastest_bootstrap_means` falls back to propagation.
+    assert_eq!(
+        None,
+        bootstrap_relative_speed_ci(&[2.0, 2.1, 1.9], &[], seed)
+    );
+    // Empty `result_times` likewise falls back.
+    assert_eq!(
+        None,
+        bootstrap_relative_speed_ci(&[], &fastest_means, seed)
+    );
+}
+
+#[test]
+fn test_bootstrap_resample_means_reused_across_comparisons() {
+    // `fastest_bootstrap_means` should carry through `config.samples` entries verbatim.
+    let config = BootstrapConfig {
+        samples: 500,
+        seed: 7,
+    };
+    let fastest_means = bootstrap_resample_means(&[1.0, 1.1, 0.9, 1.0], &config);
+    assert_eq!(500, fastest_means.len());
+
+    let (lower, upper) = bootstrap_relative_speed_ci(
+        &[2.0, 2.1, 1.9, 2.0],
+        &fastest_means,
+        seed_for_comparison(config.seed, 0),
+    )
+    .expect("non-empty timings");
+    assert!(lower <= upper);
+
+    // The same means serve a second comparison.
+    let (lower_b, upper_b) = bootstrap_relative_speed_ci(
+        &[3.0, 3.1, 2.9, 3.0],
+        &fastest_means,
+        seed_for_comparison(config.seed, 1),
+    )
+    .expect("non-empty timings");
+    assert!(lower_b <= upper_b);
+    assert!(lower_b > upper);
+}
+
+#[test]
+fn test_bootstrap_relative_speed_ci_independent_across_other_commands() {
+    // Two comparisons with identical `times` must not be forced to the same CI.
+    let config = BootstrapConfig {
+        samples: 2000,
+        seed: 7,
+    };
+    let fastest_means = bootstrap_resample_means(&[1.0, 1.1, 0.9, 1.0], &config);
+    let times = [2.0, 2.1, 1.9, 2.0];
+
+    let ci_a = bootstrap_relative_speed_ci(&times, &fastest_means, seed_for_comparison(config.seed, 0))
+        .expect("non-empty timings");
+    let ci_b = bootstrap_relative_speed_ci(&times, &fastest_means, seed_for_comparison(config.seed, 1))
+        .expect("non-empty timings");
+
+    assert_ne!(ci_a, ci_b);
 }
 
 pub fn tokenize<'a>(values: &'a str) -> Vec<String> {
@@ -213,3 +917,250 @@ fn test_tokenize_empty_values() {
     assert_eq!(tokenize(r"bar,"), vec!["bar", ""]);
     assert_eq!(tokenize(r",,"), vec!["", "", ""]);
 }
+
+#[test]
+fn test_classify_regression() {
+    let create_result = |mean| BenchmarkResult {
+        command: "cmd".into(),
+        mean,
+        stddev: 1.0,
+        median: mean,
+        user: mean,
+        system: 0.0,
+        min: mean,
+        max: mean,
+        times: None,
+        parameter: None,
+    };
+
+    let baseline_entry = BaselineEntry {
+        mean: 10.0,
+        stddev: 1.0,
+    };
+
+    assert_eq!(
+        RegressionStatus::Unchanged,
+        classify_regression(&create_result(10.5), &baseline_entry, 1.0)
+    );
+    assert_eq!(
+        RegressionStatus::Regressed,
+        classify_regression(&create_result(12.0), &baseline_entry, 1.0)
+    );
+    assert_eq!(
+        RegressionStatus::Improved,
+        classify_regression(&create_result(8.0), &baseline_entry, 1.0)
+    );
+}
+
+#[test]
+fn test_baseline_save_and_load_roundtrip() {
+    let create_result = |name: &str, mean| BenchmarkResult {
+        command: name.into(),
+        mean,
+        stddev: 0.5,
+        median: mean,
+        user: mean,
+        system: 0.0,
+        min: mean,
+        max: mean,
+        times: None,
+        parameter: None,
+    };
+
+    let results = vec![create_result("cmd1", 3.0), create_result("cmd2", 2.0)];
+    let baseline = Baseline::from_results(&results);
+
+    let path = std::env::temp_dir().join(format!(
+        "hyperfine_test_baseline_{}.json",
+        std::process::id()
+    ));
+    baseline.save(&path).expect("failed to save baseline");
+    let loaded = Baseline::load(&path).expect("failed to load baseline");
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(
+        Some(&BaselineEntry {
+            mean: 3.0,
+            stddev: 0.5
+        }),
+        loaded.get("cmd1")
+    );
+    assert_eq!(
+        Some(&BaselineEntry {
+            mean: 2.0,
+            stddev: 0.5
+        }),
+        loaded.get("cmd2")
+    );
+    assert_eq!(None, loaded.get("cmd3"));
+}
+
+#[test]
+fn test_write_benchmark_comparison_flags_regression_and_ratchets_improvement() {
+    let create_result = |name: &str, mean| BenchmarkResult {
+        command: name.into(),
+        mean,
+        stddev: 0.1,
+        median: mean,
+        user: mean,
+        system: 0.0,
+        min: mean,
+        max: mean,
+        times: None,
+        parameter: None,
+    };
+
+    let results = vec![
+        create_result("fast", 1.0),
+        create_result("mid", 2.0),
+        create_result("slow", 12.0),
+    ];
+
+    let mut baseline = Baseline::from_results(&[
+        create_result("fast", 1.0),
+        create_result("mid", 3.0),
+        create_result("slow", 10.0),
+    ]);
+    let options = BaselineComparisonOptions {
+        ratchet_noise: 1.0,
+        auto_update: true,
+    };
+
+    let any_regressed = write_benchmark_comparison(
+        &results,
+        None,
+        None,
+        Some(&mut baseline),
+        &options,
+        &BootstrapConfig::default(),
+    );
+
+    assert!(any_regressed);
+    // "mid" improved on its baseline, so auto-update ratchets it to the new timing.
+    assert_eq!(
+        Some(&BaselineEntry {
+            mean: 2.0,
+            stddev: 0.1
+        }),
+        baseline.get("mid")
+    );
+    // "fast" is unchanged against its baseline, so auto-update must leave it alone.
+    assert_eq!(
+        Some(&BaselineEntry {
+            mean: 1.0,
+            stddev: 0.1
+        }),
+        baseline.get("fast")
+    );
+}
+
+#[test]
+fn test_write_benchmark_comparison_single_result_still_checks_baseline() {
+    let result = BenchmarkResult {
+        command: "cmd".into(),
+        mean: 12.0,
+        stddev: 0.1,
+        median: 12.0,
+        user: 12.0,
+        system: 0.0,
+        min: 12.0,
+        max: 12.0,
+        times: None,
+        parameter: None,
+    };
+
+    let mut baseline = Baseline::from_results(std::slice::from_ref(&BenchmarkResult {
+        mean: 10.0,
+        ..result.clone()
+    }));
+    let options = BaselineComparisonOptions {
+        ratchet_noise: 1.0,
+        auto_update: false,
+    };
+
+    // A single-command run (e.g. `--baseline old.json --fail-on-regression` in CI)
+    // must still flag a regression rather than bailing out before the baseline check.
+    let any_regressed = write_benchmark_comparison(
+        std::slice::from_ref(&result),
+        None,
+        None,
+        Some(&mut baseline),
+        &options,
+        &BootstrapConfig::default(),
+    );
+
+    assert!(any_regressed);
+}
+
+#[test]
+fn test_mann_whitney_u_test_identical_samples_not_significant() {
+    let a = vec![1.0, 1.1, 0.9, 1.0, 1.05, 0.95];
+    let b = vec![1.0, 1.1, 0.9, 1.0, 1.05, 0.95];
+
+    let p_value = mann_whitney_u_test(&a, &b).expect("non-empty samples");
+    assert!(p_value > SIGNIFICANCE_THRESHOLD);
+}
+
+#[test]
+fn test_mann_whitney_u_test_clearly_separated_samples_significant() {
+    let a = vec![1.0, 1.1, 0.9, 1.05, 0.95, 1.02];
+    let b = vec![5.0, 5.1, 4.9, 5.05, 4.95, 5.02];
+
+    let p_value = mann_whitney_u_test(&a, &b).expect("non-empty samples");
+    assert!(p_value < SIGNIFICANCE_THRESHOLD);
+}
+
+#[test]
+fn test_mann_whitney_u_test_empty_sample() {
+    assert_eq!(None, mann_whitney_u_test(&[], &[1.0, 2.0]));
+}
+
+#[test]
+fn test_detect_outliers_no_contamination() {
+    let times = vec![1.0, 1.02, 0.98, 1.01, 0.99, 1.0, 1.03, 0.97];
+    let counts = detect_outliers(&times);
+    assert_eq!(OutlierCounts { mild: 0, severe: 0 }, counts);
+}
+
+#[test]
+fn test_detect_outliers_mild_and_severe() {
+    let mut times = vec![
+        0.8, 0.9, 1.0, 1.1, 1.2, 0.85, 0.95, 1.05, 1.15, 0.9, 1.0, 1.1, 0.95, 1.05, 1.0, 0.92,
+        1.08, 1.02, 0.98, 1.0,
+    ];
+    times[0] = 1.3; // mild: outside Q1/Q3 +/- 1.5*IQR but within 3*IQR
+    times[1] = 1.6; // severe: outside Q1/Q3 +/- 3*IQR
+    let counts = detect_outliers(&times);
+    assert_eq!(1, counts.mild);
+    assert_eq!(1, counts.severe);
+}
+
+#[test]
+fn test_detect_outliers_too_few_samples() {
+    assert_eq!(
+        OutlierCounts { mild: 0, severe: 0 },
+        detect_outliers(&[1.0, 2.0, 100.0])
+    );
+}
+
+#[test]
+fn test_format_throughput() {
+    assert_eq!("0 B/s", format_throughput(0.0, "B"));
+    assert_eq!("500 B/s", format_throughput(500.0, "B"));
+    assert_eq!("12.3 Kelem/s", format_throughput(12_300.0, "elem"));
+    assert_eq!("4.10 GB/s", format_throughput(4.1e9, "B"));
+    assert_eq!("250 Melem/s", format_throughput(2.5e8, "elem"));
+}
+
+#[test]
+fn test_format_throughput_rounds_up_to_next_magnitude() {
+    assert_eq!("1.00 MB/s", format_throughput(999_500.0, "B"));
+    assert_eq!("1.00 MB/s", format_throughput(999_999.0, "B"));
+    assert_eq!("999 KB/s", format_throughput(999_499.0, "B"));
+}
+
+#[test]
+fn test_format_throughput_rounds_up_to_next_precision_tier() {
+    assert_eq!("10.0 Kelem/s", format_throughput(9996.0, "elem"));
+    assert_eq!("100 elem/s", format_throughput(99.96, "elem"));
+}